@@ -4,6 +4,7 @@ pub extern crate xmlparser;
 extern crate codegen;
 extern crate heck;
 extern crate num_traits;
+extern crate num_bigint;
 extern crate bigdecimal;
 
 #[macro_use] pub mod macros;
@@ -21,6 +22,8 @@ pub mod parser_generator;
 mod test_parser;
 #[cfg(test)]
 mod test_parser_schema;
+#[cfg(test)]
+mod test_primitives;
 
 use support::{ParseXml, InnerStream, ParseContext};
 
@@ -1,9 +1,13 @@
 use std::cmp::max;
+use std::cmp::Ordering;
 use std::str::FromStr;
 use std::marker::PhantomData;
+use std::borrow::Cow;
 use std::fmt;
+use std::ops::{Add, Sub, Mul, Div};
 
 use bigdecimal::BigDecimal;
+use num_bigint::{BigInt, BigUint, Sign};
 use num_traits::{Zero, One};
 
 use xmlparser::{Token as XmlToken, ElementEnd, StrSpan};
@@ -11,76 +15,725 @@ use xmlparser::{Token as XmlToken, ElementEnd, StrSpan};
 use support::{ParseXml, ParseXmlStr, Stream, ParseContext, ParentContext, Facets, BigFloatNotNaN};
 use xml_utils::*;
 
+/// A facet violated during lexical validation. Carries everything needed to
+/// report every violation in a document instead of aborting on the first:
+/// the byte offset just past the end of the offending slice within the
+/// value being parsed,
+/// which facet failed, expected vs. actual, and the primitive's `NODE_NAME`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub node_name: &'static str,
+    pub facet: &'static str,
+    pub offset: usize,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}: {} violates {} (expected {})", self.node_name, self.offset, self.actual, self.facet, self.expected)
+    }
+}
+
+/// Unwraps an `Option` produced by a non-matching (not malformed) lexical
+/// form, turning `None` into an early `Ok(None)` return — the `?` operator
+/// can't do this directly since the surrounding function now returns
+/// `Result<Option<_>, ValidationError>` rather than a bare `Option`.
+macro_rules! try_opt {
+    ( $e:expr ) => {
+        match $e {
+            Some(x) => x,
+            None => return Ok(None),
+        }
+    }
+}
+
 macro_rules! return_split {
     ( $input:expr, $position:expr, $pred:expr, $validator:ident !, $facets:expr) => {{
         let input = $input;
         let pos = $position;
         let parsed = &input[0..pos];
-        $validator!(parsed, $facets);
-        return Some((&input[pos..], $pred(parsed)))
+        $validator!(parsed, $facets, Self::NODE_NAME, pos);
+        return Ok(Some((&input[pos..], $pred(parsed))))
+    }}
+}
+
+/// Like `return_split!`, but for the string primitives that carry a
+/// `whiteSpace` facet: normalizes the matched slice first (falling back to
+/// `$default_ws` when the facet wasn't set) and feeds the normalized,
+/// possibly-owned value to both the validator and the constructor.
+macro_rules! return_split_ws {
+    ( $input:expr, $position:expr, $pred:expr, $facets:expr, $default_ws:expr) => {{
+        let input = $input;
+        let pos = $position;
+        let white_space = $facets.white_space.unwrap_or($default_ws);
+        let normalized = normalize_white_space(&input[0..pos], white_space);
+        validate_str!(normalized, $facets, Self::NODE_NAME, pos);
+        return Ok(Some((&input[pos..], $pred(normalized))))
     }}
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhiteSpace {
+    Preserve,
+    Replace,
+    Collapse,
+}
+
+/// Applies the `whiteSpace` facet's `replace`/`collapse` normalization
+/// (https://www.w3.org/TR/xmlschema11-2/#rf-whiteSpace) to a matched lexical
+/// value. `preserve` is the identity and stays borrowed; `replace`/`collapse`
+/// only allocate when the input actually contains characters they change.
+fn normalize_white_space(s: &str, mode: WhiteSpace) -> Cow<'_, str> {
+    match mode {
+        WhiteSpace::Preserve => Cow::Borrowed(s),
+        WhiteSpace::Replace => {
+            if s.bytes().any(|b| b == b'\t' || b == b'\n' || b == b'\r') {
+                Cow::Owned(s.chars().map(|c| match c { '\t' | '\n' | '\r' => ' ', c => c }).collect())
+            } else {
+                Cow::Borrowed(s)
+            }
+        }
+        WhiteSpace::Collapse => {
+            let unchanged = !s.bytes().any(|b| b == b'\t' || b == b'\n' || b == b'\r')
+                && !s.starts_with(' ') && !s.ends_with(' ') && !s.contains("  ");
+            if unchanged {
+                return Cow::Borrowed(s);
+            }
+            let replaced: String = s.chars().map(|c| match c { '\t' | '\n' | '\r' => ' ', c => c }).collect();
+            let collapsed = replaced.split(' ').filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" ");
+            if collapsed == s { Cow::Borrowed(s) } else { Cow::Owned(collapsed) }
+        }
+    }
+}
+
 macro_rules! validate_str {
-    ( $s:expr, $facets:expr) => {{
+    ( $s:expr, $facets:expr, $node_name:expr, $offset:expr) => {{
         let facets = $facets;
-        let s: &&str = &$s;
+        let offset = $offset;
+        let s_ref: &str = $s.as_ref();
+        let s: &&str = &s_ref;
         if let Some(ref enumeration) = facets.enumeration {
             if !enumeration.contains(s) {
-                panic!("Expected one of {:?}, got {:?}", enumeration, s);
+                return Err(ValidationError { node_name: $node_name, facet: "enumeration", offset, expected: format!("one of {:?}", enumeration), actual: format!("{:?}", s) });
             }
         }
         if let Some(ref length) = facets.length {
             if s.len() != *length {
-                panic!("{:?} has length != {}", s, length);
+                return Err(ValidationError { node_name: $node_name, facet: "length", offset, expected: format!("length {}", length), actual: format!("{:?}", s) });
             }
         }
         if let Some(ref min_length) = facets.min_length {
             if s.len() < *min_length {
-                panic!("{:?} has length < {}", s, min_length);
+                return Err(ValidationError { node_name: $node_name, facet: "minLength", offset, expected: format!("length >= {}", min_length), actual: format!("{:?}", s) });
             }
         }
         if let Some(ref max_length) = facets.max_length {
             if s.len() > *max_length {
-                panic!("{:?} has length > {}", s, max_length);
+                return Err(ValidationError { node_name: $node_name, facet: "maxLength", offset, expected: format!("length <= {}", max_length), actual: format!("{:?}", s) });
+            }
+        }
+        if let Some(ref pattern) = facets.pattern {
+            let mut matched = false;
+            for p in pattern {
+                match pattern_matches(p, s) {
+                    Ok(true) => { matched = true; break; }
+                    Ok(false) => {}
+                    Err(()) => return Err(ValidationError { node_name: $node_name, facet: "pattern", offset, expected: format!("a schema pattern that compiles"), actual: format!("{:?}", p) }),
+                }
+            }
+            if !matched {
+                return Err(ValidationError { node_name: $node_name, facet: "pattern", offset, expected: format!("matching one of {:?}", pattern), actual: format!("{:?}", s) });
             }
         }
     }}
 }
 
 macro_rules! validate_int {
-    ( $n:expr, $facets:expr) => {{
+    ( $n:expr, $facets:expr, $node_name:expr, $offset:expr) => {{
         let n: BigDecimal = $n.into();
-        validate_decimal!(n, $facets);
+        validate_decimal!(n, $facets, $node_name, $offset);
     }}
 }
 macro_rules! validate_decimal {
-    ( $n:expr, $facets:expr) => {{
+    ( $n:expr, $facets:expr, $node_name:expr, $offset:expr) => {{
         let facets = $facets;
-        let n: BigFloatNotNaN = $n.into();
+        let offset = $offset;
+        let decimal: BigDecimal = $n;
+        let (total_digits, fraction_digits) = decimal_digit_counts(&decimal);
+        if let Some(ref max_total_digits) = facets.total_digits {
+            if total_digits > *max_total_digits {
+                return Err(ValidationError { node_name: $node_name, facet: "totalDigits", offset, expected: format!("<= {} significant digits", max_total_digits), actual: format!("{} ({} digits)", decimal, total_digits) });
+            }
+        }
+        if let Some(ref max_fraction_digits) = facets.fraction_digits {
+            if fraction_digits > *max_fraction_digits {
+                return Err(ValidationError { node_name: $node_name, facet: "fractionDigits", offset, expected: format!("<= {} fraction digits", max_fraction_digits), actual: format!("{} ({} fraction digits)", decimal, fraction_digits) });
+            }
+        }
+        let n: BigFloatNotNaN = decimal.into();
         if let Some(ref min_exclusive) = facets.min_exclusive {
             if n <= *min_exclusive {
-                panic!("{} is <= {}", n, min_exclusive);
+                return Err(ValidationError { node_name: $node_name, facet: "minExclusive", offset, expected: format!("> {}", min_exclusive), actual: format!("{}", n) });
             }
         }
         if let Some(ref min_inclusive) = facets.min_inclusive {
             if n < *min_inclusive {
-                panic!("{} is < {}", n, min_inclusive);
+                return Err(ValidationError { node_name: $node_name, facet: "minInclusive", offset, expected: format!(">= {}", min_inclusive), actual: format!("{}", n) });
             }
         }
         if let Some(ref max_exclusive) = facets.max_exclusive {
             if n >= *max_exclusive {
-                panic!("{} is >= {}", n, max_exclusive);
+                return Err(ValidationError { node_name: $node_name, facet: "maxExclusive", offset, expected: format!("< {}", max_exclusive), actual: format!("{}", n) });
             }
         }
         if let Some(ref max_inclusive) = facets.max_inclusive {
             if n > *max_inclusive {
-                panic!("{} is > {}", n, max_inclusive);
+                return Err(ValidationError { node_name: $node_name, facet: "maxInclusive", offset, expected: format!("<= {}", max_inclusive), actual: format!("{}", n) });
+            }
+        }
+    }}
+}
+
+/// Like `validate_decimal!`, but for `duration`, whose `months`/`seconds`
+/// can't be reduced to one scalar (a month's length varies) — bound checks
+/// go through `duration_facet_cmp` instead, which applies the same
+/// four-reference-instant technique as `Duration`'s `PartialOrd`. An
+/// indeterminate comparison at any reference is not a violation: XSD's
+/// partial order only rejects a value once it is *determinately* out of
+/// bounds. `totalDigits`/`fractionDigits` don't apply to `duration`.
+macro_rules! validate_duration {
+    ( $months:expr, $seconds:expr, $facets:expr, $node_name:expr, $offset:expr) => {{
+        let facets = $facets;
+        let offset = $offset;
+        let months = $months;
+        let seconds = $seconds;
+        let actual = || format!("P{}M{}S", months, seconds);
+        if let Some(ref min_exclusive) = facets.min_exclusive {
+            let cmp = duration_facet_cmp(months, &seconds, min_exclusive);
+            if cmp == Some(Ordering::Less) || cmp == Some(Ordering::Equal) {
+                return Err(ValidationError { node_name: $node_name, facet: "minExclusive", offset, expected: format!("> {}", min_exclusive), actual: actual() });
+            }
+        }
+        if let Some(ref min_inclusive) = facets.min_inclusive {
+            if duration_facet_cmp(months, &seconds, min_inclusive) == Some(Ordering::Less) {
+                return Err(ValidationError { node_name: $node_name, facet: "minInclusive", offset, expected: format!(">= {}", min_inclusive), actual: actual() });
+            }
+        }
+        if let Some(ref max_exclusive) = facets.max_exclusive {
+            let cmp = duration_facet_cmp(months, &seconds, max_exclusive);
+            if cmp == Some(Ordering::Greater) || cmp == Some(Ordering::Equal) {
+                return Err(ValidationError { node_name: $node_name, facet: "maxExclusive", offset, expected: format!("< {}", max_exclusive), actual: actual() });
+            }
+        }
+        if let Some(ref max_inclusive) = facets.max_inclusive {
+            if duration_facet_cmp(months, &seconds, max_inclusive) == Some(Ordering::Greater) {
+                return Err(ValidationError { node_name: $node_name, facet: "maxInclusive", offset, expected: format!("<= {}", max_inclusive), actual: actual() });
             }
         }
     }}
 }
 
+/// A compiled node of an XSD regular expression (https://www.w3.org/TR/xmlschema11-2/#regexs).
+/// The XSD dialect is implicitly anchored at both ends (no `^`/`$`), so a
+/// `RegexNode` always matches the *entire* value rather than a substring.
+#[derive(Clone)]
+enum RegexNode {
+    Literal(char),
+    AnyChar,
+    Class(CharClass),
+    Concat(Vec<RegexNode>),
+    Alt(Vec<RegexNode>),
+    Group(Box<RegexNode>),
+    Repeat(Box<RegexNode>, usize, Option<usize>),
+}
+
+#[derive(Clone)]
+enum ClassItem {
+    Char(char),
+    Range(char, char),
+    Predicate(bool, fn(char) -> bool),
+}
+
+/// A `[...]` character class, including XSD's class-subtraction extension
+/// (`[a-z-[aeiou]]`): a char belongs to the class if it matches `items`
+/// (negated as a whole by `negated`) and is not also matched by `subtract`.
+#[derive(Clone)]
+struct CharClass {
+    negated: bool,
+    items: Vec<ClassItem>,
+    subtract: Option<Box<CharClass>>,
+}
+
+impl CharClass {
+    fn predicate(negated: bool, pred: fn(char) -> bool) -> CharClass {
+        CharClass { negated: false, items: vec![ClassItem::Predicate(negated, pred)], subtract: None }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        let mut matched = self.items.iter().any(|item| match *item {
+            ClassItem::Char(ch) => ch == c,
+            ClassItem::Range(lo, hi) => lo <= c && c <= hi,
+            ClassItem::Predicate(negate, pred) => pred(c) != negate,
+        });
+        if self.negated {
+            matched = !matched;
+        }
+        if matched {
+            if let Some(ref subtract) = self.subtract {
+                if subtract.matches(c) {
+                    matched = false;
+                }
+            }
+        }
+        matched
+    }
+}
+
+/// Resolves a `\p{Name}`/`\P{Name}` Unicode escape to a predicate: either a
+/// general category (the single-letter `L`, `N`, `P`, `Z`, `C` and the
+/// two-letter `Lu`, `Ll`, `Nd`, `Zs`, `Cc`, each approximated with `char`'s
+/// own classification methods; `M` and `S` are not recognized),
+/// or, when `Name` starts with `Is`, one of the Unicode blocks in
+/// `unicode_block_predicate`. Anything else isn't recognized; see
+/// `pattern_matches` for why that's a hard compile failure rather than a
+/// predicate that quietly matches nothing.
+fn unicode_category_predicate(name: &str) -> Option<fn(char) -> bool> {
+    if let Some(block) = name.strip_prefix("Is") {
+        return unicode_block_predicate(block);
+    }
+    match name {
+        "L" => Some(char::is_alphabetic),
+        "Lu" => Some(|c: char| c.is_uppercase()),
+        "Ll" => Some(|c: char| c.is_lowercase()),
+        "N" => Some(|c: char| c.is_numeric()),
+        "Nd" => Some(|c: char| c.is_ascii_digit() || (c.is_numeric() && !c.is_alphabetic())),
+        "P" => Some(|c: char| c.is_ascii_punctuation()),
+        "Z" | "Zs" => Some(char::is_whitespace),
+        "C" | "Cc" => Some(|c: char| c.is_control()),
+        _ => None,
+    }
+}
+
+/// A handful of the Unicode blocks XSD patterns commonly reference via
+/// `\p{IsName}` (https://www.w3.org/TR/xmlschema11-2/#regexs, which defers to
+/// the Unicode block names from `Blocks.txt`, stripped of whitespace). Not
+/// exhaustive; an unrecognized block name is a compile failure, not a
+/// predicate that matches nothing (see `pattern_matches`).
+fn unicode_block_predicate(block: &str) -> Option<fn(char) -> bool> {
+    match block {
+        "BasicLatin" => Some(|c: char| ('\u{0000}'..='\u{007F}').contains(&c)),
+        "Latin-1Supplement" => Some(|c: char| ('\u{0080}'..='\u{00FF}').contains(&c)),
+        "LatinExtended-A" => Some(|c: char| ('\u{0100}'..='\u{017F}').contains(&c)),
+        "LatinExtended-B" => Some(|c: char| ('\u{0180}'..='\u{024F}').contains(&c)),
+        "Greek" => Some(|c: char| ('\u{0370}'..='\u{03FF}').contains(&c)),
+        "Cyrillic" => Some(|c: char| ('\u{0400}'..='\u{04FF}').contains(&c)),
+        "Hebrew" => Some(|c: char| ('\u{0590}'..='\u{05FF}').contains(&c)),
+        "Arabic" => Some(|c: char| ('\u{0600}'..='\u{06FF}').contains(&c)),
+        "Hiragana" => Some(|c: char| ('\u{3040}'..='\u{309F}').contains(&c)),
+        "Katakana" => Some(|c: char| ('\u{30A0}'..='\u{30FF}').contains(&c)),
+        "CJKUnifiedIdeographs" => Some(|c: char| ('\u{4E00}'..='\u{9FFF}').contains(&c)),
+        _ => None,
+    }
+}
+
+/// Recursive-descent parser for the XSD regex dialect, producing a `RegexNode`.
+struct PatternParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PatternParser {
+    fn new(pattern: &str) -> PatternParser {
+        PatternParser { chars: pattern.chars().collect(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).cloned()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn parse_alt(&mut self) -> Option<RegexNode> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.peek() == Some('|') {
+            self.bump();
+            branches.push(self.parse_concat()?);
+        }
+        if branches.len() == 1 { branches.pop() } else { Some(RegexNode::Alt(branches)) }
+    }
+
+    fn parse_concat(&mut self) -> Option<RegexNode> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            nodes.push(self.parse_quantified()?);
+        }
+        Some(RegexNode::Concat(nodes))
+    }
+
+    fn parse_quantified(&mut self) -> Option<RegexNode> {
+        let atom = self.parse_atom()?;
+        match self.peek() {
+            Some('?') => { self.bump(); Some(RegexNode::Repeat(Box::new(atom), 0, Some(1))) }
+            Some('*') => { self.bump(); Some(RegexNode::Repeat(Box::new(atom), 0, None)) }
+            Some('+') => { self.bump(); Some(RegexNode::Repeat(Box::new(atom), 1, None)) }
+            Some('{') => {
+                self.bump();
+                let min = self.parse_number()?;
+                let max = if self.peek() == Some(',') {
+                    self.bump();
+                    if self.peek() == Some('}') { None } else { Some(self.parse_number()?) }
+                } else {
+                    Some(min)
+                };
+                if self.bump() != Some('}') { return None; }
+                Some(RegexNode::Repeat(Box::new(atom), min, max))
+            }
+            _ => Some(atom),
+        }
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let mut digits = String::new();
+        while let Some(c) = self.peek() {
+            if !c.is_ascii_digit() { break; }
+            digits.push(c);
+            self.bump();
+        }
+        if digits.is_empty() { return None; }
+        digits.parse().ok()
+    }
+
+    fn parse_atom(&mut self) -> Option<RegexNode> {
+        match self.bump()? {
+            '(' => {
+                let inner = self.parse_alt()?;
+                if self.bump() != Some(')') { return None; }
+                Some(RegexNode::Group(Box::new(inner)))
+            }
+            '.' => Some(RegexNode::AnyChar),
+            '[' => self.parse_class().map(RegexNode::Class),
+            '\\' => self.parse_escape(),
+            c => Some(RegexNode::Literal(c)),
+        }
+    }
+
+    fn parse_escape(&mut self) -> Option<RegexNode> {
+        match self.bump()? {
+            'd' => Some(RegexNode::Class(CharClass::predicate(false, |c| c.is_ascii_digit()))),
+            'D' => Some(RegexNode::Class(CharClass::predicate(true, |c| c.is_ascii_digit()))),
+            's' => Some(RegexNode::Class(CharClass::predicate(false, is_xsd_whitespace))),
+            'S' => Some(RegexNode::Class(CharClass::predicate(true, is_xsd_whitespace))),
+            'w' => Some(RegexNode::Class(CharClass::predicate(false, |c| c.is_alphanumeric() || c == '_'))),
+            'W' => Some(RegexNode::Class(CharClass::predicate(true, |c| c.is_alphanumeric() || c == '_'))),
+            'i' => Some(RegexNode::Class(CharClass::predicate(false, is_name_start_char))),
+            'I' => Some(RegexNode::Class(CharClass::predicate(true, is_name_start_char))),
+            'c' => Some(RegexNode::Class(CharClass::predicate(false, is_name_char))),
+            'C' => Some(RegexNode::Class(CharClass::predicate(true, is_name_char))),
+            escape @ ('p' | 'P') => {
+                if self.bump() != Some('{') { return None; }
+                let mut name = String::new();
+                while let Some(c) = self.peek() {
+                    if c == '}' { break; }
+                    name.push(c);
+                    self.bump();
+                }
+                if self.bump() != Some('}') { return None; }
+                let pred = unicode_category_predicate(&name)?;
+                Some(RegexNode::Class(CharClass::predicate(escape == 'P', pred)))
+            }
+            'n' => Some(RegexNode::Literal('\n')),
+            'r' => Some(RegexNode::Literal('\r')),
+            't' => Some(RegexNode::Literal('\t')),
+            other => Some(RegexNode::Literal(other)),
+        }
+    }
+
+    /// Parses the body of a `[...]`/`[^...]` class, called just after the
+    /// opening `[` has been consumed.
+    fn parse_class(&mut self) -> Option<CharClass> {
+        let negated = if self.peek() == Some('^') { self.bump(); true } else { false };
+        let mut items = Vec::new();
+        let mut subtract = None;
+        loop {
+            match self.peek()? {
+                ']' => { self.bump(); break; }
+                '-' if self.peek_at(1) == Some('[') => {
+                    self.bump();
+                    self.bump();
+                    subtract = Some(Box::new(self.parse_class()?));
+                    if self.bump() != Some(']') { return None; }
+                    break;
+                }
+                _ => {
+                    let lo = self.parse_class_char()?;
+                    if let ClassItem::Char(lo_char) = lo {
+                        if self.peek() == Some('-') && self.peek_at(1) != Some('[') && self.peek_at(1) != Some(']') {
+                            self.bump();
+                            match self.parse_class_char()? {
+                                ClassItem::Char(hi_char) => { items.push(ClassItem::Range(lo_char, hi_char)); continue; }
+                                other => { items.push(ClassItem::Char(lo_char)); items.push(other); continue; }
+                            }
+                        }
+                    }
+                    items.push(lo);
+                }
+            }
+        }
+        Some(CharClass { negated, items, subtract })
+    }
+
+    fn parse_class_char(&mut self) -> Option<ClassItem> {
+        match self.bump()? {
+            '\\' => match self.bump()? {
+                'd' => Some(ClassItem::Predicate(false, |c| c.is_ascii_digit())),
+                'D' => Some(ClassItem::Predicate(true, |c| c.is_ascii_digit())),
+                's' => Some(ClassItem::Predicate(false, is_xsd_whitespace)),
+                'S' => Some(ClassItem::Predicate(true, is_xsd_whitespace)),
+                'w' => Some(ClassItem::Predicate(false, |c| c.is_alphanumeric() || c == '_')),
+                'W' => Some(ClassItem::Predicate(true, |c| c.is_alphanumeric() || c == '_')),
+                'i' => Some(ClassItem::Predicate(false, is_name_start_char)),
+                'I' => Some(ClassItem::Predicate(true, is_name_start_char)),
+                'c' => Some(ClassItem::Predicate(false, is_name_char)),
+                'C' => Some(ClassItem::Predicate(true, is_name_char)),
+                'n' => Some(ClassItem::Char('\n')),
+                'r' => Some(ClassItem::Char('\r')),
+                't' => Some(ClassItem::Char('\t')),
+                other => Some(ClassItem::Char(other)),
+            },
+            c => Some(ClassItem::Char(c)),
+        }
+    }
+}
+
+/// The `totalDigits`/`fractionDigits` facets count significant digits on the
+/// *canonical* form of the value: leading zeros are never part of the
+/// unscaled magnitude to begin with, and trailing zeros after the decimal
+/// point are trimmed, but trailing zeros before it are part of the number's
+/// magnitude and do count (e.g. `1200` has 4 total digits, `1.20` has 2).
+fn decimal_digit_counts(n: &BigDecimal) -> (usize, usize) {
+    let (unscaled, exponent) = n.as_bigint_and_exponent();
+    let digits = unscaled.to_string();
+    let mut digits = digits.trim_start_matches('-').to_string();
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    if digits == "0" {
+        return (1, 0);
+    }
+    if exponent <= 0 {
+        let trailing_zeros = (-exponent) as usize;
+        return (digits.len() + trailing_zeros, 0);
+    }
+    let mut fraction_digits = exponent as usize;
+    while fraction_digits > 0 && digits.len() > 1 && digits.ends_with('0') {
+        digits.pop();
+        fraction_digits -= 1;
+    }
+    (digits.len(), fraction_digits)
+}
+
+fn is_xsd_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\n' || c == '\r'
+}
+
+fn compile_pattern(pattern: &str) -> Option<RegexNode> {
+    let mut parser = PatternParser::new(pattern);
+    let node = parser.parse_alt()?;
+    if parser.pos != parser.chars.len() { return None; }
+    Some(node)
+}
+
+/// A single instruction of the compiled NFA program (Russ Cox / Ken
+/// Thompson-style VM, in the tradition of `regex-automata`'s Pike VM): a
+/// `RegexNode` tree is flattened into this flat instruction list so that
+/// matching never recurses per matched character. This is what makes
+/// unbounded quantifiers (`*`, `+`, `{n,}`) safe against arbitrarily long
+/// input — the match loop below is a plain `for` loop over the value's
+/// characters, not a call stack that grows with it.
+enum Instr {
+    Char(CharMatcher),
+    Split(usize, usize),
+    Jmp(usize),
+    Match,
+}
+
+enum CharMatcher {
+    Literal(char),
+    Any,
+    Class(CharClass),
+}
+
+impl CharMatcher {
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            CharMatcher::Literal(lit) => lit == c,
+            CharMatcher::Any => true,
+            CharMatcher::Class(ref class) => class.matches(c),
+        }
+    }
+}
+
+/// Flattens a `RegexNode` into a program, appended to `out`. `{min,max}`
+/// bounds are unrolled into straight-line copies of `inner` at compile time
+/// (bounded by the *pattern's* own text, not by any matched value's length);
+/// the open-ended tail of `*`/`+`/`{n,}` compiles to a `Split`/`Jmp` loop
+/// instead of being unrolled, since it has no fixed bound. Returns `None` if
+/// `node` contains a `{min,max}` bound with `max < min`, which can never be
+/// satisfied (mirrors `pattern_matches`'s policy of treating a malformed
+/// pattern as a hard compile failure rather than a silent non-match).
+fn compile_node(node: RegexNode, out: &mut Vec<Instr>) -> Option<()> {
+    match node {
+        RegexNode::Literal(c) => out.push(Instr::Char(CharMatcher::Literal(c))),
+        RegexNode::AnyChar => out.push(Instr::Char(CharMatcher::Any)),
+        RegexNode::Class(class) => out.push(Instr::Char(CharMatcher::Class(class))),
+        RegexNode::Concat(nodes) => {
+            for n in nodes { compile_node(n, out)?; }
+        }
+        RegexNode::Alt(branches) => compile_alt(branches, out)?,
+        RegexNode::Group(inner) => compile_node(*inner, out)?,
+        RegexNode::Repeat(inner, min, max) => compile_repeat(*inner, min, max, out)?,
+    }
+    Some(())
+}
+
+fn compile_alt(branches: Vec<RegexNode>, out: &mut Vec<Instr>) -> Option<()> {
+    let mut branches = branches.into_iter().peekable();
+    let mut jmp_ends = Vec::new();
+    while let Some(branch) = branches.next() {
+        if branches.peek().is_some() {
+            let split_at = out.len();
+            out.push(Instr::Split(0, 0)); // patched below
+            let left = out.len();
+            compile_node(branch, out)?;
+            let jmp_at = out.len();
+            out.push(Instr::Jmp(0)); // patched below
+            let right = out.len();
+            out[split_at] = Instr::Split(left, right);
+            jmp_ends.push(jmp_at);
+        } else {
+            compile_node(branch, out)?;
+        }
+    }
+    let end = out.len();
+    for jmp_at in jmp_ends {
+        out[jmp_at] = Instr::Jmp(end);
+    }
+    Some(())
+}
+
+fn compile_repeat(inner: RegexNode, min: usize, max: Option<usize>, out: &mut Vec<Instr>) -> Option<()> {
+    if max.map_or(false, |m| m < min) { return None; }
+    for _ in 0..min {
+        compile_node(inner.clone(), out)?;
+    }
+    match max {
+        Some(max) => {
+            // Each optional repetition beyond `min` is its own Split that
+            // skips straight past it; chaining them sequentially needs no
+            // bookkeeping since "skip" always lands on the next instruction.
+            for _ in min..max {
+                let split_at = out.len();
+                out.push(Instr::Split(0, 0)); // patched below
+                let enter = out.len();
+                compile_node(inner.clone(), out)?;
+                let after = out.len();
+                out[split_at] = Instr::Split(enter, after);
+            }
+        }
+        None => {
+            // Unbounded tail: a loop that keeps trying another repetition
+            // before falling through. A thread that re-enters `loop_at`
+            // without having consumed a character (inner matched zero-width)
+            // is deduplicated by `add_thread`'s `visited` set, so this can't
+            // spin forever the way naive backtracking recursion would on
+            // patterns like `(a?)*`.
+            let loop_at = out.len();
+            out.push(Instr::Split(0, 0)); // patched below
+            let enter = out.len();
+            compile_node(inner, out)?;
+            out.push(Instr::Jmp(loop_at));
+            let after = out.len();
+            out[loop_at] = Instr::Split(enter, after);
+        }
+    }
+    Some(())
+}
+
+/// Adds `pc` and everything reachable from it via `Split`/`Jmp` (its epsilon
+/// closure) to `list`, deduplicating via `visited`. Iterative with an
+/// explicit stack rather than recursive, so epsilon closures of arbitrary
+/// size (e.g. deeply nested alternation) can't overflow the native stack
+/// either — though in practice the program size is bounded by the pattern
+/// text, not by the value being matched.
+fn add_thread(prog: &[Instr], pc: usize, list: &mut Vec<usize>, visited: &mut [bool]) {
+    let mut stack = vec![pc];
+    while let Some(pc) = stack.pop() {
+        if visited[pc] { continue; }
+        visited[pc] = true;
+        match prog[pc] {
+            Instr::Jmp(target) => stack.push(target),
+            Instr::Split(a, b) => { stack.push(a); stack.push(b); }
+            Instr::Char(_) | Instr::Match => list.push(pc),
+        }
+    }
+}
+
+/// Runs the compiled program against `chars`, advancing one character per
+/// iteration of this `for` loop — the thread list can grow with the
+/// program's size (bounded by the pattern), never with `chars`'s length, so
+/// this can't stack-overflow on a long value the way a per-character
+/// recursive matcher would.
+fn run_program(prog: &[Instr], chars: &[char]) -> bool {
+    let mut clist = Vec::new();
+    add_thread(prog, 0, &mut clist, &mut vec![false; prog.len()]);
+    for pos in 0..=chars.len() {
+        if pos == chars.len() {
+            return clist.iter().any(|&pc| matches!(prog[pc], Instr::Match));
+        }
+        let mut nlist = Vec::new();
+        let mut visited = vec![false; prog.len()];
+        for &pc in &clist {
+            if let Instr::Char(ref matcher) = prog[pc] {
+                if matcher.matches(chars[pos]) {
+                    add_thread(prog, pc + 1, &mut nlist, &mut visited);
+                }
+            }
+        }
+        if nlist.is_empty() { return false; }
+        clist = nlist;
+    }
+    false
+}
+
+/// Compiles `pattern` and matches it against `value`. `Err(())` means
+/// `pattern` itself failed to compile (e.g. an unrecognized `\p{Is...}`
+/// block, or a `{min,max}` bound with `max < min`) — a defect in the
+/// schema, not a mismatch in `value` — which the caller must surface as its
+/// own facet violation rather than treating the pattern as unconditionally
+/// unsatisfied.
+fn pattern_matches(pattern: &str, value: &str) -> Result<bool, ()> {
+    let node = compile_pattern(pattern).ok_or(())?;
+    let mut prog = Vec::new();
+    compile_node(node, &mut prog).ok_or(())?;
+    prog.push(Instr::Match);
+    let chars: Vec<char> = value.chars().collect();
+    Ok(run_program(&prog, &chars))
+}
+
 pub const PRIMITIVE_TYPES: &[(&'static str, &'static str)] = &[
     ("anySimpleType", "AnySimpleType"),
     ("token", "Token"),
@@ -93,48 +746,794 @@ pub const PRIMITIVE_TYPES: &[(&'static str, &'static str)] = &[
     ("nonNegativeInteger", "NonNegativeInteger"),
     ("dateTime", "DateTime"),
     ("date", "Date"),
+    ("time", "Time"),
+    ("gYear", "GYear"),
+    ("gYearMonth", "GYearMonth"),
+    ("gMonth", "GMonth"),
+    ("gDay", "GDay"),
+    ("gMonthDay", "GMonthDay"),
     ("duration", "Duration"),
     ("decimal", "Decimal"),
     ];
 
-pub type DateTime<'input> = Token<'input>; // TODO
-pub type Date<'input> = Token<'input>; // TODO
-pub type Duration<'input> = Token<'input>; // TODO
+/// Day count of `y-m-d` since the epoch (1970-01-01), proleptic Gregorian, per
+/// Howard Hinnant's `days_from_civil` algorithm. Valid for any `y` (including
+/// negative/BCE years) and any `1 <= m <= 12`, `1 <= d <= days_in_month(y, m)`.
+fn days_from_civil(y: i64, m: u8, d: u8) -> i128 {
+    let y = y as i128 - if m <= 2 { 1 } else { 0 };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as i128; // [0, 399]
+    let mp = ((m as i128) + 9) % 12; // [0, 11], Mar=0 .. Feb=11
+    let doy = (153 * mp + 2) / 5 + d as i128 - 1; // [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // [0, 146096]
+    era * 146097 + doe - 719468
+}
+
+fn is_leap_year(y: i64) -> bool {
+    (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
+}
+
+fn days_in_month(y: i64, m: u8) -> u8 {
+    match m {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => if is_leap_year(y) { 29 } else { 28 },
+        _ => 0,
+    }
+}
+
+/// Seconds since the epoch for a civil date/time in the given timezone offset
+/// (minutes east of UTC), as an exact `BigDecimal` (fractional seconds preserved).
+fn instant_seconds(year: i64, month: u8, day: u8, hour: u8, minute: u8, second: &BigDecimal, tz_minutes: i32) -> BigDecimal {
+    let days = days_from_civil(year, month, day);
+    let whole = days * 86400 + (hour as i128) * 3600 + (minute as i128) * 60 - (tz_minutes as i128) * 60;
+    BigDecimal::from(BigInt::from(whole)) + second
+}
+
+type LocalFields<'a> = (i64, u8, u8, u8, u8, &'a BigDecimal);
+
+/// The XSD partial order on timezone-optional date/time values: if both
+/// values carry a timezone (or neither does), compare their normalized UTC
+/// instants directly. If exactly one lacks a timezone, compare it against the
+/// other shifted by both `+14:00` and `-14:00` (the legal timezone span) and
+/// only return an order if both comparisons agree; otherwise the values are
+/// indeterminate (`None`), per https://www.w3.org/TR/xmlschema11-2/#dateTime.
+fn compare_partial_instant(self_local: LocalFields, self_tz: Option<i32>, other_local: LocalFields, other_tz: Option<i32>) -> Option<Ordering> {
+    let (sy, sm, sd, sh, smi, ss) = self_local;
+    let (oy, om, od, oh, omi, os) = other_local;
+    match (self_tz, other_tz) {
+        (Some(stz), Some(otz)) => {
+            instant_seconds(sy, sm, sd, sh, smi, ss, stz).partial_cmp(&instant_seconds(oy, om, od, oh, omi, os, otz))
+        }
+        (None, None) => {
+            instant_seconds(sy, sm, sd, sh, smi, ss, 0).partial_cmp(&instant_seconds(oy, om, od, oh, omi, os, 0))
+        }
+        (Some(stz), None) => {
+            let a = instant_seconds(sy, sm, sd, sh, smi, ss, stz);
+            let high = instant_seconds(oy, om, od, oh, omi, os, 14 * 60);
+            let low = instant_seconds(oy, om, od, oh, omi, os, -14 * 60);
+            let cmp_high = a.partial_cmp(&high);
+            let cmp_low = a.partial_cmp(&low);
+            if cmp_high == cmp_low { cmp_high } else { None }
+        }
+        (None, Some(otz)) => {
+            let b = instant_seconds(oy, om, od, oh, omi, os, otz);
+            let high = instant_seconds(sy, sm, sd, sh, smi, ss, 14 * 60);
+            let low = instant_seconds(sy, sm, sd, sh, smi, ss, -14 * 60);
+            let cmp_high = high.partial_cmp(&b);
+            let cmp_low = low.partial_cmp(&b);
+            if cmp_high == cmp_low { cmp_high } else { None }
+        }
+    }
+}
+
+/// Parses `[+-]hh:mm` or `Z`, returning the offset in minutes east of UTC.
+/// Goes through `parse_digits` (rather than slicing fixed byte ranges
+/// directly) so a multi-byte UTF-8 character anywhere in a malformed
+/// timezone suffix yields `None` instead of panicking on a mid-codepoint
+/// slice boundary.
+fn parse_timezone(input: &str) -> Option<(&str, Option<i32>)> {
+    match input.chars().next() {
+        Some('Z') => Some((&input[1..], Some(0))),
+        Some(sign @ ('+' | '-')) => {
+            let (rest, hh) = parse_digits(&input[1..], 2)?;
+            if rest.as_bytes().get(0) != Some(&b':') { return None; }
+            let (rest, mm) = parse_digits(&rest[1..], 2)?;
+            if hh > 14 || mm > 59 || (hh == 14 && mm != 0) { return None; }
+            let minutes = (hh * 60 + mm) as i32;
+            let minutes = if sign == '-' { -minutes } else { minutes };
+            Some((rest, Some(minutes)))
+        }
+        _ => Some((input, None)),
+    }
+}
+
+fn parse_digits(input: &str, count: usize) -> Option<(&str, i64)> {
+    if input.len() < count || !input.as_bytes()[..count].iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    Some((&input[count..], input[..count].parse().ok()?))
+}
+
+/// `-?YYYY`, where the year may be wider than 4 digits (but not have leading
+/// zeros beyond the mandatory 4); the whole value may be negated (a BCE
+/// year: -1 is "2 BCE" in the proleptic Gregorian calendar XSD uses). There is
+/// no year `0000`/`-0000`: XSD years skip straight from `-0001` to `0001`.
+/// Shared by `parse_date_fields` and the `gYear`/`gYearMonth` primitives.
+fn parse_year_field(input: &str) -> Option<(&str, i64)> {
+    let (input, negative) = match input.chars().next() {
+        Some('-') => (&input[1..], true),
+        _ => (input, false),
+    };
+    let mut digits = 0;
+    for c in input.chars() {
+        if c.is_ascii_digit() { digits += 1; } else { break; }
+    }
+    if digits < 4 { return None; }
+    if digits > 4 && input.as_bytes()[0] == b'0' { return None; }
+    let (input, year) = parse_digits(input, digits)?;
+    if year == 0 { return None; }
+    let year = if negative { -year } else { year };
+    Some((input, year))
+}
+
+/// `-?YYYY-MM-DD`.
+fn parse_date_fields(input: &str) -> Option<(&str, i64, u8, u8)> {
+    let (input, year) = parse_year_field(input)?;
+    if input.as_bytes().get(0) != Some(&b'-') { return None; }
+    let (input, month) = parse_digits(&input[1..], 2)?;
+    if month < 1 || month > 12 { return None; }
+    if input.as_bytes().get(0) != Some(&b'-') { return None; }
+    let (input, day) = parse_digits(&input[1..], 2)?;
+    if day < 1 || day as u8 > days_in_month(year, month as u8) { return None; }
+    Some((input, year, month as u8, day as u8))
+}
+
+/// `hh:mm:ss(.s+)?`, rejecting leap seconds (`:60`) and normalizing the
+/// legal-but-special `24:00:00` to the start of the following day.
+fn parse_time_fields(input: &str) -> Option<(&str, u8, u8, BigDecimal, bool)> {
+    let (input, hour) = parse_digits(input, 2)?;
+    if input.as_bytes().get(0) != Some(&b':') { return None; }
+    let (input, minute) = parse_digits(&input[1..], 2)?;
+    if input.as_bytes().get(0) != Some(&b':') { return None; }
+    let (mut input, whole_second) = parse_digits(&input[1..], 2)?;
+    let mut second_str = format!("{}", whole_second);
+    if input.as_bytes().get(0) == Some(&b'.') {
+        let mut end = 1;
+        for c in input[1..].chars() {
+            if c.is_ascii_digit() { end += 1; } else { break; }
+        }
+        if end == 1 { return None; }
+        second_str.push_str(&input[0..end]);
+        input = &input[end..];
+    }
+    let second = BigDecimal::from_str(&second_str).ok()?;
+    if hour > 24 || minute > 59 || second >= BigDecimal::from(60) { return None; }
+    let is_midnight_24 = hour == 24 && minute == 0 && second == BigDecimal::zero();
+    if hour == 24 && !is_midnight_24 { return None; }
+    Some((input, hour as u8, minute as u8, second, is_midnight_24))
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#dateTime
+#[derive(Debug, Clone)]
+pub struct DateTime<'input> {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: BigDecimal,
+    /// Offset in minutes east of UTC; `None` means the lexical form omitted
+    /// a timezone, which affects ordering (see `PartialOrd`).
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> DateTime<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(self.year, self.month, self.day, self.hour, self.minute, &self.second, self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for DateTime<'input> {
+    const NODE_NAME: &'static str = "dateTime";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, DateTime<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, year, month, day) = try_opt!(parse_date_fields(input));
+        if input.as_bytes().get(0) != Some(&b'T') { return Ok(None); }
+        let (input, hour, minute, second, midnight_24) = try_opt!(parse_time_fields(&input[1..]));
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let (year, month, day, hour) = if midnight_24 {
+            let (y, m, d) = next_day(year, month, day);
+            (y, m, d, 0)
+        } else {
+            (year, month, day, hour)
+        };
+        let res = DateTime { year, month, day, hour, minute, second, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for DateTime<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for DateTime<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_partial_instant(
+            (self.year, self.month, self.day, self.hour, self.minute, &self.second), self.timezone,
+            (other.year, other.month, other.day, other.hour, other.minute, &other.second), other.timezone,
+        )
+    }
+}
+
+fn next_day(year: i64, month: u8, day: u8) -> (i64, u8, u8) {
+    if day < days_in_month(year, month) {
+        (year, month, day + 1)
+    } else if month < 12 {
+        (year, month + 1, 1)
+    } else {
+        (year + 1, 1, 1)
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#date
+#[derive(Debug, Clone)]
+pub struct Date<'input> {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> Date<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(self.year, self.month, self.day, 0, 0, &BigDecimal::zero(), self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for Date<'input> {
+    const NODE_NAME: &'static str = "date";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Date<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, year, month, day) = try_opt!(parse_date_fields(input));
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let res = Date { year, month, day, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for Date<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for Date<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let zero = BigDecimal::zero();
+        compare_partial_instant(
+            (self.year, self.month, self.day, 0, 0, &zero), self.timezone,
+            (other.year, other.month, other.day, 0, 0, &zero), other.timezone,
+        )
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#time
+///
+/// XSD compares `time` values as if they shared the reference date
+/// `1972-12-31` (https://www.w3.org/TR/xmlschema11-2/#time), since a `time`
+/// has no date fields of its own.
+#[derive(Debug, Clone)]
+pub struct Time<'input> {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: BigDecimal,
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> Time<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(1972, 12, 31, self.hour, self.minute, &self.second, self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for Time<'input> {
+    const NODE_NAME: &'static str = "time";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Time<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, hour, minute, second, midnight_24) = try_opt!(parse_time_fields(input));
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let hour = if midnight_24 { 0 } else { hour };
+        let res = Time { hour, minute, second, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for Time<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for Time<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_partial_instant(
+            (1972, 12, 31, self.hour, self.minute, &self.second), self.timezone,
+            (1972, 12, 31, other.hour, other.minute, &other.second), other.timezone,
+        )
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#gYear
+///
+/// Ordering fixes the month/day at the reference `01-01`.
+#[derive(Debug, Clone)]
+pub struct GYear<'input> {
+    pub year: i64,
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> GYear<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(self.year, 1, 1, 0, 0, &BigDecimal::zero(), self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for GYear<'input> {
+    const NODE_NAME: &'static str = "gYear";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, GYear<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, year) = try_opt!(parse_year_field(input));
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let res = GYear { year, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for GYear<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for GYear<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let zero = BigDecimal::zero();
+        compare_partial_instant(
+            (self.year, 1, 1, 0, 0, &zero), self.timezone,
+            (other.year, 1, 1, 0, 0, &zero), other.timezone,
+        )
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#gYearMonth
+///
+/// Ordering fixes the day at the reference `01`.
+#[derive(Debug, Clone)]
+pub struct GYearMonth<'input> {
+    pub year: i64,
+    pub month: u8,
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> GYearMonth<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(self.year, self.month, 1, 0, 0, &BigDecimal::zero(), self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for GYearMonth<'input> {
+    const NODE_NAME: &'static str = "gYearMonth";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, GYearMonth<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, year) = try_opt!(parse_year_field(input));
+        if input.as_bytes().get(0) != Some(&b'-') { return Ok(None); }
+        let (input, month) = try_opt!(parse_digits(&input[1..], 2));
+        if month < 1 || month > 12 { return Ok(None); }
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let res = GYearMonth { year, month: month as u8, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for GYearMonth<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for GYearMonth<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let zero = BigDecimal::zero();
+        compare_partial_instant(
+            (self.year, self.month, 1, 0, 0, &zero), self.timezone,
+            (other.year, other.month, 1, 0, 0, &zero), other.timezone,
+        )
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#gMonth
+///
+/// Ordering fixes the year/day at the reference `1972-MM-01`.
+#[derive(Debug, Clone)]
+pub struct GMonth<'input> {
+    pub month: u8,
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> GMonth<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(1972, self.month, 1, 0, 0, &BigDecimal::zero(), self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for GMonth<'input> {
+    const NODE_NAME: &'static str = "gMonth";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, GMonth<'input>)>, ValidationError> {
+        let original_len = input.len();
+        if input.as_bytes().get(0) != Some(&b'-') || input.as_bytes().get(1) != Some(&b'-') { return Ok(None); }
+        let (input, month) = try_opt!(parse_digits(&input[2..], 2));
+        if month < 1 || month > 12 { return Ok(None); }
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let res = GMonth { month: month as u8, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for GMonth<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for GMonth<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let zero = BigDecimal::zero();
+        compare_partial_instant(
+            (1972, self.month, 1, 0, 0, &zero), self.timezone,
+            (1972, other.month, 1, 0, 0, &zero), other.timezone,
+        )
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#gDay
+///
+/// Ordering fixes the year/month at the reference `1972-01-DD` (January
+/// always has 31 days, so every legal `DD` is valid there).
+#[derive(Debug, Clone)]
+pub struct GDay<'input> {
+    pub day: u8,
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> GDay<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(1972, 1, self.day, 0, 0, &BigDecimal::zero(), self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for GDay<'input> {
+    const NODE_NAME: &'static str = "gDay";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, GDay<'input>)>, ValidationError> {
+        let original_len = input.len();
+        if input.as_bytes().get(0) != Some(&b'-') || input.as_bytes().get(1) != Some(&b'-') || input.as_bytes().get(2) != Some(&b'-') { return Ok(None); }
+        let (input, day) = try_opt!(parse_digits(&input[3..], 2));
+        if day < 1 || day > 31 { return Ok(None); }
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let res = GDay { day: day as u8, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for GDay<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for GDay<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let zero = BigDecimal::zero();
+        compare_partial_instant(
+            (1972, 1, self.day, 0, 0, &zero), self.timezone,
+            (1972, 1, other.day, 0, 0, &zero), other.timezone,
+        )
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#gMonthDay
+///
+/// Ordering fixes the year at the reference `1972` (a leap year, so
+/// `--02-29` is a valid reference instant).
+#[derive(Debug, Clone)]
+pub struct GMonthDay<'input> {
+    pub month: u8,
+    pub day: u8,
+    pub timezone: Option<i32>,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> GMonthDay<'input> {
+    fn instant(&self) -> BigDecimal {
+        instant_seconds(1972, self.month, self.day, 0, 0, &BigDecimal::zero(), self.timezone.unwrap_or(0))
+    }
+}
+
+impl<'input> ParseXmlStr<'input> for GMonthDay<'input> {
+    const NODE_NAME: &'static str = "gMonthDay";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, GMonthDay<'input>)>, ValidationError> {
+        let original_len = input.len();
+        if input.as_bytes().get(0) != Some(&b'-') || input.as_bytes().get(1) != Some(&b'-') { return Ok(None); }
+        let (input, month) = try_opt!(parse_digits(&input[2..], 2));
+        if month < 1 || month > 12 { return Ok(None); }
+        if input.as_bytes().get(0) != Some(&b'-') { return Ok(None); }
+        let (input, day) = try_opt!(parse_digits(&input[1..], 2));
+        if day < 1 || day as u8 > days_in_month(1972, month as u8) { return Ok(None); }
+        let (input, timezone) = try_opt!(parse_timezone(input));
+        let res = GMonthDay { month: month as u8, day: day as u8, timezone, _marker: PhantomData };
+        validate_decimal!(res.instant(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for GMonthDay<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'input> PartialOrd for GMonthDay<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let zero = BigDecimal::zero();
+        compare_partial_instant(
+            (1972, self.month, self.day, 0, 0, &zero), self.timezone,
+            (1972, other.month, other.day, 0, 0, &zero), other.timezone,
+        )
+    }
+}
+
+fn parse_uint_unit(input: &str, unit: u8) -> Option<(&str, Option<i64>)> {
+    let bytes = input.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() && bytes[len].is_ascii_digit() { len += 1; }
+    if len == 0 || bytes.get(len) != Some(&unit) {
+        return Some((input, None));
+    }
+    let n: i64 = input[0..len].parse().ok()?;
+    Some((&input[len+1..], Some(n)))
+}
+
+fn parse_decimal_unit(input: &str, unit: u8) -> Option<(&str, Option<BigDecimal>)> {
+    let bytes = input.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() && bytes[len].is_ascii_digit() { len += 1; }
+    if len < bytes.len() && bytes[len] == b'.' {
+        let mut flen = len + 1;
+        while flen < bytes.len() && bytes[flen].is_ascii_digit() { flen += 1; }
+        if flen > len + 1 { len = flen; }
+    }
+    if len == 0 || bytes.get(len) != Some(&unit) {
+        return Some((input, None));
+    }
+    let n = BigDecimal::from_str(&input[0..len]).ok()?;
+    Some((&input[len+1..], Some(n)))
+}
+
+struct RawDuration {
+    months: i64,
+    seconds: BigDecimal,
+    has_year_or_month: bool,
+    has_day_or_time: bool,
+}
+
+/// `-?PnYnMnDTnHnMnS`: every field is optional, at least one must be present,
+/// and `T` must appear iff a time field (`H`/`M`/`S`) follows it. Years fold
+/// into `months`, days/hours/minutes fold into `seconds`; the sign applies to
+/// both components.
+fn parse_duration_raw(input: &str) -> Option<(&str, RawDuration)> {
+    let (input, negative) = match input.chars().next() {
+        Some('-') => (&input[1..], true),
+        _ => (input, false),
+    };
+    if input.chars().next() != Some('P') { return None; }
+    let input = &input[1..];
+
+    let (input, years) = parse_uint_unit(input, b'Y')?;
+    let (input, months_field) = parse_uint_unit(input, b'M')?;
+    let (input, days) = parse_uint_unit(input, b'D')?;
+
+    let (input, hours, minutes, secs, has_time) = if input.chars().next() == Some('T') {
+        let input = &input[1..];
+        let (input, h) = parse_uint_unit(input, b'H')?;
+        let (input, m) = parse_uint_unit(input, b'M')?;
+        let (input, s) = parse_decimal_unit(input, b'S')?;
+        if h.is_none() && m.is_none() && s.is_none() { return None; }
+        (input, h, m, s, true)
+    } else {
+        (input, None, None, None, false)
+    };
+
+    let has_year_or_month = years.is_some() || months_field.is_some();
+    let has_day_or_time = days.is_some() || has_time;
+    if !has_year_or_month && !has_day_or_time {
+        return None;
+    }
+
+    let months = years.unwrap_or(0) * 12 + months_field.unwrap_or(0);
+    let seconds = BigDecimal::from(days.unwrap_or(0)) * BigDecimal::from(86400)
+        + BigDecimal::from(hours.unwrap_or(0)) * BigDecimal::from(3600)
+        + BigDecimal::from(minutes.unwrap_or(0)) * BigDecimal::from(60)
+        + secs.unwrap_or_else(BigDecimal::zero);
+
+    let (months, seconds) = if negative { (-months, -seconds) } else { (months, seconds) };
+    Some((input, RawDuration { months, seconds, has_year_or_month, has_day_or_time }))
+}
+
+/// The four XSD reference dateTimes (`1696-09-01T00:00:00Z`,
+/// `1697-02-01T00:00:00Z`, `1903-03-01T00:00:00Z`, `1903-07-01T00:00:00Z`)
+/// used to order durations: months cannot be compared to seconds directly
+/// (a month's length varies), so the order relation instead compares what
+/// adding each duration to every reference instant produces.
+const DURATION_REFERENCES: [(i64, u8, u8); 4] = [
+    (1696, 9, 1),
+    (1697, 2, 1),
+    (1903, 3, 1),
+    (1903, 7, 1),
+];
+
+fn add_duration_to_reference(year: i64, month: u8, day: u8, months: i64, seconds: &BigDecimal) -> BigDecimal {
+    let total_months = year * 12 + (month as i64 - 1) + months;
+    let new_year = total_months.div_euclid(12);
+    let new_month = (total_months.rem_euclid(12) + 1) as u8;
+    instant_seconds(new_year, new_month, day, 0, 0, &BigDecimal::zero(), 0) + seconds
+}
+
+/// Compares `(months, seconds)` against a scalar facet bound (itself a
+/// pure-seconds, zero-months duration) using the same
+/// four-reference-instant technique as `Duration`'s `PartialOrd`: at each
+/// reference, the months component is converted to calendar-correct seconds
+/// by diffing against that reference with `months` zeroed out, so a month
+/// that spans a reference's leap day contributes differently than one that
+/// doesn't — exactly the source of XSD's indeterminate duration comparisons.
+fn duration_facet_cmp(months: i64, seconds: &BigDecimal, bound: &BigFloatNotNaN) -> Option<Ordering> {
+    let results: Vec<Option<Ordering>> = DURATION_REFERENCES.iter().map(|&(y, m, d)| {
+        let with_months = add_duration_to_reference(y, m, d, months, seconds);
+        let without_months = add_duration_to_reference(y, m, d, 0, &BigDecimal::zero());
+        let deviation: BigFloatNotNaN = (with_months - without_months).into();
+        deviation.partial_cmp(bound)
+    }).collect();
+    let first = results[0]?;
+    if results.iter().all(|&o| o == Some(first)) { Some(first) } else { None }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#duration
+#[derive(Debug, Clone)]
+pub struct Duration<'input> {
+    pub months: i64,
+    pub seconds: BigDecimal,
+    _marker: PhantomData<&'input ()>,
+}
+
+impl<'input> ParseXmlStr<'input> for Duration<'input> {
+    const NODE_NAME: &'static str = "duration";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Duration<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, raw) = try_opt!(parse_duration_raw(input));
+        let res = Duration { months: raw.months, seconds: raw.seconds, _marker: PhantomData };
+        validate_duration!(res.months, res.seconds.clone(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+impl<'input> PartialEq for Duration<'input> {
+    fn eq(&self, other: &Self) -> bool {
+        self.months == other.months && self.seconds == other.seconds
+    }
+}
+
+impl<'input> PartialOrd for Duration<'input> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let results: Vec<Option<Ordering>> = DURATION_REFERENCES.iter().map(|&(y, m, d)| {
+            let a = add_duration_to_reference(y, m, d, self.months, &self.seconds);
+            let b = add_duration_to_reference(y, m, d, other.months, &other.seconds);
+            a.partial_cmp(&b)
+        }).collect();
+        let first = results[0]?;
+        if results.iter().all(|&o| o == Some(first)) { Some(first) } else { None }
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#yearMonthDuration
+///
+/// A restriction of `duration` that rejects any day/time component.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct YearMonthDuration<'input>(pub i64, PhantomData<&'input ()>);
+impl<'input> ParseXmlStr<'input> for YearMonthDuration<'input> {
+    const NODE_NAME: &'static str = "yearMonthDuration";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, YearMonthDuration<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, raw) = try_opt!(parse_duration_raw(input));
+        if raw.has_day_or_time {
+            return Ok(None);
+        }
+        let res = YearMonthDuration(raw.months, PhantomData);
+        validate_int!(res.0, facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#dayTimeDuration
+///
+/// A restriction of `duration` that rejects any year/month component.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct DayTimeDuration<'input>(pub BigDecimal, PhantomData<&'input ()>);
+impl<'input> ParseXmlStr<'input> for DayTimeDuration<'input> {
+    const NODE_NAME: &'static str = "dayTimeDuration";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, DayTimeDuration<'input>)>, ValidationError> {
+        let original_len = input.len();
+        let (input, raw) = try_opt!(parse_duration_raw(input));
+        if raw.has_year_or_month {
+            return Ok(None);
+        }
+        let res = DayTimeDuration(raw.seconds.clone(), PhantomData);
+        validate_decimal!(res.0.clone(), facets, Self::NODE_NAME, original_len - input.len());
+        Ok(Some((input, res)))
+    }
+}
 
 /// https://www.w3.org/TR/xmlschema11-2/#token
+///
+/// `whiteSpace` defaults to `collapse` for `token` (and everything derived
+/// from it), so the lexical value may need normalizing; `Cow` keeps the
+/// common `preserve`/no-op case zero-copy.
 #[derive(Debug, PartialEq)]
-pub struct Token<'input>(pub &'input str);
+pub struct Token<'input>(pub Cow<'input, str>);
 
 impl<'input> ParseXmlStr<'input> for Token<'input> {
     const NODE_NAME: &'static str = "token";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Token<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Token<'input>)>, ValidationError> {
         if input.len() == 0 {
-            return None;
+            return Ok(None);
         }
         let mut iter = input.char_indices().peekable();
         while let Some((i, c)) = iter.next() {
             match (i, c) {
-                (0, ' ') => return None,
+                (0, ' ') => return Ok(None),
                 (_, ' ') => {
                     // If this space is followed by a whitespace, split before both
                     match iter.peek() {
                         Some((_, ' ')) | Some((_, '\r')) | Some((_, '\n')) |
-                        Some((_, '\t')) => return_split!(input, i, Token, validate_str!, facets),
+                        Some((_, '\t')) => return_split_ws!(input, i, Token, facets, WhiteSpace::Collapse),
                         Some((_, _)) => (),
-                        None => return_split!(input, i, Token, validate_str!, facets),
+                        None => return_split_ws!(input, i, Token, facets, WhiteSpace::Collapse),
                     }
                 }
-                (_, '\r') | (_, '\n') | (_, '\t') => return_split!(input, i, Token, validate_str!, facets),
+                (_, '\r') | (_, '\n') | (_, '\t') => return_split_ws!(input, i, Token, facets, WhiteSpace::Collapse),
                 _ => (),
             }
         }
-        validate_str!(input, facets);
-        Some(("", Token(input)))
+        return_split_ws!(input, input.len(), Token, facets, WhiteSpace::Collapse)
     }
 }
 impl<'input> Default for Token<'input> {
     fn default() -> Self {
-        Token("")
+        Token(Cow::Borrowed(""))
     }
 }
 
@@ -145,9 +1544,9 @@ pub struct QName<'input> {
 }
 impl<'input> ParseXmlStr<'input> for QName<'input> {
     const NODE_NAME: &'static str = "QName";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, QName<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, QName<'input>)>, ValidationError> {
         if input.len() == 0 {
-            return None;
+            return Ok(None);
         }
         let f = &mut |prefix, local| QName {
             namespace: parent_context.namespaces.get(prefix).cloned(),
@@ -160,21 +1559,21 @@ impl<'input> ParseXmlStr<'input> for QName<'input> {
             }
             else if c == ' ' { // TODO
                 if i == 0 || i <= i1+1 {
-                    return None;
+                    return Ok(None);
                 }
                 if i1 > 0 {
-                    return Some((&input[i..], f(&input[0..i1+1], &input[i1+1..i+1])))
+                    return Ok(Some((&input[i..], f(&input[0..i1+1], &input[i1+1..i+1]))))
                 }
                 else {
-                    return Some((&input[i..], f("", &input[0..i+1])))
+                    return Ok(Some((&input[i..], f("", &input[0..i+1]))))
                 }
             }
         }
         if i1 > 0 {
-            return Some(("", f(&input[0..i1], &input[i1+1..])))
+            return Ok(Some(("", f(&input[0..i1], &input[i1+1..]))))
         }
         else {
-            return Some(("", f("", input)))
+            return Ok(Some(("", f("", input))))
         }
     }
 }
@@ -214,19 +1613,20 @@ impl<'input> fmt::Display for QName<'input> {
 pub struct AnyUri<'input>(pub &'input str);
 impl<'input> ParseXmlStr<'input> for AnyUri<'input> {
     const NODE_NAME: &'static str = "AnyUri";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, AnyUri<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, AnyUri<'input>)>, ValidationError> {
         if input.len() == 0 {
-            return None;
+            return Ok(None);
         }
         for (i, c) in input.char_indices() {
             if c == ' ' { // TODO
                 if i == 0 {
-                    return None;
+                    return Ok(None);
                 }
-                return Some((&input[i..], AnyUri(&input[0..i])))
+                return_split!(input, i, AnyUri, validate_str!, facets);
             }
         }
-        Some(("", AnyUri(input)))
+        validate_str!(input, facets, Self::NODE_NAME, input.len());
+        Ok(Some(("", AnyUri(input))))
     }
 }
 
@@ -242,94 +1642,103 @@ impl<'input> ParseXml<'input> for AnyURIElement<'input> {
     }
 }
 
+/// `xsd:integer` is unbounded, so this holds a `BigInt` (and
+/// `NonNegativeInteger`/`PositiveInteger` a `BigUint`) rather than a
+/// machine word — a schema is free to put
+/// `minInclusive`/`maxInclusive` far outside `i64` range, and identifiers or
+/// counters in real-world documents do show up outside it.
 #[derive(Debug, PartialEq, Default)]
-pub struct Integer<'input>(pub i64, PhantomData<&'input ()>);
+pub struct Integer<'input>(pub BigInt, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for Integer<'input> {
     const NODE_NAME: &'static str = "Integer";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Integer<'input>)> {
-        let mut iter = input.char_indices();
-        let mut n: i64 = 0;
-        let mut multiplier = 1;
-        let c = iter.next()?.1;
-        match c {
-            '+' => multiplier = 1,
-            '-' => multiplier = -1,
-            '0'..='9' => n = (c as i64) - ('0' as i64),
-            _ => return None,
-        }
-
-        if c == '+' || c == '-' {
-            let c = iter.next()?.1;
-            match c {
-                '0'..='9' => n = (c as i64) - ('0' as i64),
-                _ => return None,
-            }
-        }
-
-        for (i,c) in iter {
-            match c {
-                '0'..='9' => n = n * 10 + ((c as i64) - ('0' as i64)),
-                _ => {
-                    let res = multiplier * n;
-                    validate_int!(res, facets);
-                    return Some((&input[i..], Integer(res, PhantomData::default())));
-                }
-            }
-        }
-        
-        let res = multiplier * n;
-        validate_int!(res, facets);
-        Some(("", Integer(res, PhantomData::default())))
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Integer<'input>)>, ValidationError> {
+        let bytes = input.as_bytes();
+        let (negative, start) = match bytes.get(0) {
+            Some(b'+') => (false, 1),
+            Some(b'-') => (true, 1),
+            Some(b'0'..=b'9') => (false, 0),
+            _ => return Ok(None),
+        };
+        let mut end = start;
+        while bytes.get(end).map_or(false, u8::is_ascii_digit) { end += 1; }
+        if end == start { return Ok(None); }
+        let magnitude = try_opt!(BigUint::parse_bytes(&bytes[start..end], 10));
+        let sign = if negative { Sign::Minus } else { Sign::Plus };
+        let res = BigInt::from_biguint(sign, magnitude);
+        validate_int!(res.clone(), facets, Self::NODE_NAME, end);
+        Ok(Some((&input[end..], Integer(res, PhantomData::default()))))
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct NonNegativeInteger<'input>(pub u64, PhantomData<&'input ()>);
+#[derive(Debug, PartialEq, Default)]
+pub struct NonNegativeInteger<'input>(pub BigUint, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for NonNegativeInteger<'input> {
     const NODE_NAME: &'static str = "NonNegativeInteger";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets) -> Option<(&'input str, NonNegativeInteger<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets) -> Result<Option<(&'input str, NonNegativeInteger<'input>)>, ValidationError> {
         let min = max(BigFloatNotNaN::zero(), facets.min_inclusive.clone().unwrap_or(BigFloatNotNaN::zero()));
         let mut facets = facets.clone();
         facets.min_inclusive = Some(min);
-        let (output, n) = Integer::parse_self_xml_str(input, parse_context, parent_context, &facets)?;
-        Some((output, NonNegativeInteger(n.0 as u64, PhantomData::default())))
+        let (output, n) = try_opt!(Integer::parse_self_xml_str(input, parse_context, parent_context, &facets)?);
+        // `min_inclusive` was floored at zero above, so `n.0` is guaranteed non-negative.
+        let magnitude = n.0.to_biguint().expect("floored at zero by min_inclusive facet");
+        Ok(Some((output, NonNegativeInteger(magnitude, PhantomData::default()))))
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct PositiveInteger<'input>(pub u64, PhantomData<&'input ()>);
+#[derive(Debug, PartialEq, Default)]
+pub struct PositiveInteger<'input>(pub BigUint, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for PositiveInteger<'input> {
     const NODE_NAME: &'static str = "PositiveInteger";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets) -> Option<(&'input str, PositiveInteger<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets) -> Result<Option<(&'input str, PositiveInteger<'input>)>, ValidationError> {
         let min = max(BigFloatNotNaN::one(), facets.min_inclusive.clone().unwrap_or(BigFloatNotNaN::zero()));
         let mut facets = facets.clone();
         facets.min_inclusive = Some(min);
-        let (output, n) = NonNegativeInteger::parse_self_xml_str(input, parse_context, parent_context, &facets)?;
-        Some((output, PositiveInteger(n.0, PhantomData::default())))
+        let (output, n) = try_opt!(NonNegativeInteger::parse_self_xml_str(input, parse_context, parent_context, &facets)?);
+        Ok(Some((output, PositiveInteger(n.0, PhantomData::default()))))
     }
 }
 
-#[derive(Debug, PartialEq, Default)]
+// Stays on `BigDecimal` rather than a fixed-point `i128`: totalDigits/fractionDigits
+// only need the canonical digit count, which `as_bigint_and_exponent` already gives us.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
 pub struct Decimal<'input>(pub BigDecimal, PhantomData<&'input ()>);
+
+impl<'input> Add for Decimal<'input> {
+    type Output = Decimal<'input>;
+    fn add(self, other: Decimal<'input>) -> Decimal<'input> { Decimal(self.0 + other.0, PhantomData) }
+}
+impl<'input> Sub for Decimal<'input> {
+    type Output = Decimal<'input>;
+    fn sub(self, other: Decimal<'input>) -> Decimal<'input> { Decimal(self.0 - other.0, PhantomData) }
+}
+impl<'input> Mul for Decimal<'input> {
+    type Output = Decimal<'input>;
+    fn mul(self, other: Decimal<'input>) -> Decimal<'input> { Decimal(self.0 * other.0, PhantomData) }
+}
+impl<'input> Div for Decimal<'input> {
+    type Output = Decimal<'input>;
+    fn div(self, other: Decimal<'input>) -> Decimal<'input> { Decimal(self.0 / other.0, PhantomData) }
+}
+
 impl<'input> ParseXmlStr<'input> for Decimal<'input> {
     const NODE_NAME: &'static str = "Decimal";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Decimal<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Decimal<'input>)>, ValidationError> {
         for (i, c) in input.char_indices() {
             if c == ' ' { // TODO
                 let res = match BigDecimal::from_str(&input[0..i]) {
                     Ok(res) => res,
-                    Err(_) => return None,
+                    Err(_) => return Ok(None),
                 };
-                validate_decimal!(res.clone(), facets);
-                return Some((&input[i..], Decimal(res, PhantomData::default())))
+                validate_decimal!(res.clone(), facets, Self::NODE_NAME, i);
+                return Ok(Some((&input[i..], Decimal(res, PhantomData::default()))))
             }
         }
         let res = match BigDecimal::from_str(input) {
             Ok(res) => res,
-            Err(_) => return None,
+            Err(_) => return Ok(None),
         };
-        validate_decimal!(res.clone(), facets);
-        Some(("", Decimal(res, PhantomData::default())))
+        validate_decimal!(res.clone(), facets, Self::NODE_NAME, input.len());
+        Ok(Some(("", Decimal(res, PhantomData::default()))))
     }
 }
 
@@ -384,24 +1793,27 @@ impl<'input> ParseXml<'input> for Any<'input> {
 }
 
 /// https://www.w3.org/TR/xmlschema11-2/#string
+///
+/// `whiteSpace` defaults to `preserve` for `string`, so this stays
+/// zero-copy unless a schema explicitly tightens the facet.
 #[derive(Debug, PartialEq)]
-pub struct XmlString<'input>(pub &'input str);
+pub struct XmlString<'input>(pub Cow<'input, str>);
 
 impl<'input> ParseXmlStr<'input> for XmlString<'input> {
     const NODE_NAME: &'static str = "XmlString";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, XmlString<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, XmlString<'input>)>, ValidationError> {
         for (i, c) in input.char_indices() {
             if !is_xml_char(c) {
-                return_split!(input, i, XmlString, validate_str!, facets);
+                return_split_ws!(input, i, XmlString, facets, WhiteSpace::Preserve);
             }
         }
-        Some(("", XmlString(input)))
+        return_split_ws!(input, input.len(), XmlString, facets, WhiteSpace::Preserve)
     }
 }
 
 impl<'input> Default for XmlString<'input> {
     fn default() -> Self {
-        XmlString("")
+        XmlString(Cow::Borrowed(""))
     }
 }
 
@@ -411,8 +1823,8 @@ pub struct AnySimpleType<'input>(pub &'input str);
 
 impl<'input> ParseXmlStr<'input> for AnySimpleType<'input> {
     const NODE_NAME: &'static str = "AnySimpleType";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, AnySimpleType<'input>)> {
-        Some(("", AnySimpleType(input)))
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, AnySimpleType<'input>)>, ValidationError> {
+        Ok(Some(("", AnySimpleType(input))))
     }
 }
 
@@ -424,23 +1836,25 @@ impl<'input> Default for AnySimpleType<'input> {
 
 
 /// https://www.w3.org/TR/xmlschema11-2/#NCName
+///
+/// `NCName` derives from `token`, so `whiteSpace` defaults to `collapse`.
 #[derive(Debug, PartialEq)]
-pub struct NcName<'input>(pub &'input str);
+pub struct NcName<'input>(pub Cow<'input, str>);
 
 impl<'input> ParseXmlStr<'input> for NcName<'input> {
     const NODE_NAME: &'static str = "NcName";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, NcName<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, NcName<'input>)>, ValidationError> {
         let mut iter = input.char_indices();
-        let c = iter.next()?.1;
-        if c == ':' || !is_name_start_char(c) { return None };
+        let c = try_opt!(iter.next()).1;
+        if c == ':' || !is_name_start_char(c) { return Ok(None) };
 
         for (i, c) in iter {
             if c == ':' || !is_name_char(c) {
-                return_split!(input, i, NcName, validate_str!, facets);
+                return_split_ws!(input, i, NcName, facets, WhiteSpace::Collapse);
             }
         }
 
-        Some(("", NcName(input)))
+        return_split_ws!(input, input.len(), NcName, facets, WhiteSpace::Collapse)
     }
 }
 
@@ -448,20 +1862,20 @@ impl<'input> ParseXmlStr<'input> for NcName<'input> {
 pub struct Boolean<'input>(bool, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for Boolean<'input> {
     const NODE_NAME: &'static str = "Boolean";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Boolean<'input>)> {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Boolean<'input>)>, ValidationError> {
         if input.len() >= 1 {
             match &input[0..1] {
-                "0" => return Some((&input[1..], Boolean(false, PhantomData::default()))),
-                "1" => return Some((&input[1..], Boolean(true, PhantomData::default()))),
+                "0" => return Ok(Some((&input[1..], Boolean(false, PhantomData::default())))),
+                "1" => return Ok(Some((&input[1..], Boolean(true, PhantomData::default())))),
                 _ => (),
             }
         }
         if input.len() >= 4 && &input[0..4] == "true" {
-            return Some((&input[4..], Boolean(true, PhantomData::default())))
+            return Ok(Some((&input[4..], Boolean(true, PhantomData::default()))))
         }
         if input.len() >= 5 && &input[0..4] == "false" {
-            return Some((&input[5..], Boolean(false, PhantomData::default())))
+            return Ok(Some((&input[5..], Boolean(false, PhantomData::default()))))
         }
-        None
+        Ok(None)
     }
 }
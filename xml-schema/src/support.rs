@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use xmlparser::Token as XmlToken;
+
+pub use bigfloat::BigFloatNotNaN;
+use primitives::{ValidationError, WhiteSpace};
+
+/// Per-node lexical constraints (https://www.w3.org/TR/xmlschema11-2/#facets),
+/// threaded down from the enclosing `xs:restriction` to whichever primitive
+/// is parsing the current value.
+#[derive(Debug, Clone, Default)]
+pub struct Facets<'a> {
+    pub enumeration: Option<Vec<&'a str>>,
+    pub length: Option<usize>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<Vec<String>>,
+    pub white_space: Option<WhiteSpace>,
+    pub total_digits: Option<usize>,
+    pub fraction_digits: Option<usize>,
+    pub min_exclusive: Option<BigFloatNotNaN>,
+    pub min_inclusive: Option<BigFloatNotNaN>,
+    pub max_exclusive: Option<BigFloatNotNaN>,
+    pub max_inclusive: Option<BigFloatNotNaN>,
+}
+
+/// Namespace prefixes in scope at the current point in the document, used to
+/// resolve a `QName`'s prefix to its URI.
+#[derive(Debug, Clone, Default)]
+pub struct ParentContext<'input> {
+    pub namespaces: HashMap<&'input str, &'input str>,
+}
+
+/// Per-parse mutable state threaded through every `parse_self_xml`/
+/// `parse_self_xml_str` call; a document parse always starts from `Default`.
+pub trait ParseContext<'input>: Default {}
+
+/// A snapshot of a `Stream`'s position, taken by `Stream::transaction` and
+/// rewound by `rollback` if the speculative parse it guarded didn't pan out.
+pub struct Transaction {
+    pos: usize,
+}
+
+impl Transaction {
+    pub fn rollback<'input>(&self, stream: &mut Stream<'input>) {
+        stream.set_position(self.pos);
+    }
+}
+
+/// A rewindable cursor over a token stream, used by node parsers that need to
+/// look ahead and back out of a tentative match (see `Any`'s implementation).
+pub trait Stream<'input> {
+    fn next(&mut self) -> Option<XmlToken<'input>>;
+    fn position(&self) -> usize;
+    fn set_position(&mut self, pos: usize);
+
+    fn transaction(&self) -> Transaction {
+        Transaction { pos: self.position() }
+    }
+}
+
+/// A lexical value parser that consumes from a string slice rather than a
+/// token stream (the simple/atomic XSD types). Returns `Ok(None)` for a
+/// non-matching (not malformed) lexical form, and `Err` for a value that
+/// matched the lexical grammar but violated one of `facets`.
+pub trait ParseXmlStr<'input>: Sized {
+    const NODE_NAME: &'static str;
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Result<Option<(&'input str, Self)>, ValidationError>;
+}
+
+/// A node parser that consumes directly from the token stream (elements and
+/// `xs:any` wildcards).
+pub trait ParseXml<'input>: Sized {
+    const NODE_NAME: &'static str;
+    fn parse_self_xml<TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Self>;
+}
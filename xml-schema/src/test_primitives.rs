@@ -0,0 +1,257 @@
+use std::str::FromStr;
+use std::cmp::Ordering;
+
+use bigdecimal::BigDecimal;
+use bigfloat::BigFloatNotNaN;
+
+use primitives::*;
+use support::{Facets, ParentContext, ParseContext, ParseXmlStr};
+
+#[derive(Default)]
+struct TestContext;
+impl<'input> ParseContext<'input> for TestContext {}
+
+fn parse<'input, T: ParseXmlStr<'input>>(input: &'input str, facets: &Facets) -> T {
+    let mut ctx = TestContext::default();
+    let parent = ParentContext::default();
+    T::parse_self_xml_str(input, &mut ctx, &parent, facets).unwrap().unwrap().1
+}
+
+fn parse_err<'input, T: ParseXmlStr<'input>>(input: &'input str, facets: &Facets) -> ValidationError {
+    let mut ctx = TestContext::default();
+    let parent = ParentContext::default();
+    match T::parse_self_xml_str(input, &mut ctx, &parent, facets) {
+        Err(e) => e,
+        Ok(_) => panic!("expected a facet violation parsing {:?}", input),
+    }
+}
+
+#[test]
+fn date_time_orders_by_instant_across_timezones() {
+    // 00:00-05:00 is 05:00Z, ten hours after 00:00+05:00 (19:00Z the day before).
+    let later: DateTime = parse("2024-01-01T00:00:00-05:00", &Facets::default());
+    let earlier: DateTime = parse("2024-01-01T00:00:00+05:00", &Facets::default());
+    assert!(later > earlier);
+}
+
+#[test]
+fn date_time_indeterminate_when_only_one_side_has_a_timezone() {
+    // Within the +/-14:00 window a timezone-less value can't be compared
+    // against a timezoned one without knowing its implicit timezone.
+    let naive: DateTime = parse("2024-01-01T12:00:00", &Facets::default());
+    let zoned: DateTime = parse("2024-01-01T12:00:00Z", &Facets::default());
+    assert_eq!(naive.partial_cmp(&zoned), None);
+}
+
+#[test]
+fn year_zero_is_rejected() {
+    assert!(DateTime::parse_self_xml_str("0000-01-01T00:00:00", &mut TestContext::default(), &ParentContext::default(), &Facets::default()).unwrap().is_none());
+    assert!(DateTime::parse_self_xml_str("-0000-01-01T00:00:00", &mut TestContext::default(), &ParentContext::default(), &Facets::default()).unwrap().is_none());
+}
+
+#[test]
+fn far_future_year_orders_correctly_without_overflow() {
+    let near: DateTime = parse("2024-01-01T00:00:00Z", &Facets::default());
+    let far: DateTime = parse("290000000000-01-01T00:00:00Z", &Facets::default());
+    assert!(far > near);
+}
+
+#[test]
+fn g_year_month_and_g_month_day_parse_and_order() {
+    let a: GYearMonth = parse("2023-06", &Facets::default());
+    let b: GYearMonth = parse("2023-07", &Facets::default());
+    assert!(a < b);
+
+    let a: GMonthDay = parse("--06-01", &Facets::default());
+    let b: GMonthDay = parse("--06-02", &Facets::default());
+    assert!(a < b);
+}
+
+#[test]
+fn duration_is_indeterminate_between_one_month_and_twenty_eight_days() {
+    // The textbook XSD example: P1M can be 28, 29, 30 or 31 days depending on
+    // which reference instant it's added to, so it's incomparable to P28D.
+    let one_month: Duration = parse("P1M", &Facets::default());
+    let twenty_eight_days: Duration = parse("P28D", &Facets::default());
+    assert_eq!(one_month.partial_cmp(&twenty_eight_days), None);
+}
+
+#[test]
+fn duration_one_month_is_greater_than_twenty_seven_days() {
+    let one_month: Duration = parse("P1M", &Facets::default());
+    let twenty_seven_days: Duration = parse("P27D", &Facets::default());
+    assert_eq!(one_month.partial_cmp(&twenty_seven_days), Some(Ordering::Greater));
+}
+
+#[test]
+fn duration_facet_min_inclusive_accounts_for_months() {
+    let mut facets = Facets::default();
+    // minInclusive="P365D" (a year is always >= 365 days and a month never
+    // is, so this boundary is unambiguous regardless of which reference
+    // instant P1M or P2Y lands on).
+    facets.min_inclusive = Some(BigFloatNotNaN::from(BigDecimal::from(365 * 86400)));
+
+    let err = parse_err::<Duration>("P1M", &facets);
+    assert_eq!(err.facet, "minInclusive");
+
+    let _: Duration = parse("P2Y", &facets);
+}
+
+#[test]
+fn pattern_facet_matches_literal_alternation() {
+    let mut facets = Facets::default();
+    facets.pattern = Some(vec!["foo|bar".to_string()]);
+    let _: Token = parse("bar", &facets);
+    let err = parse_err::<Token>("baz", &facets);
+    assert_eq!(err.facet, "pattern");
+}
+
+#[test]
+fn pattern_facet_unicode_block_escape_matches() {
+    let mut facets = Facets::default();
+    facets.pattern = Some(vec![r"\p{IsBasicLatin}+".to_string()]);
+    let _: Token = parse("hello", &facets);
+}
+
+#[test]
+fn pattern_facet_unrecognized_block_escape_is_a_hard_error() {
+    let mut facets = Facets::default();
+    facets.pattern = Some(vec![r"\p{IsNotARealBlock}+".to_string()]);
+    let err = parse_err::<Token>("hello", &facets);
+    assert_eq!(err.facet, "pattern");
+    assert_eq!(err.expected, "a schema pattern that compiles");
+}
+
+#[test]
+fn pattern_facet_unbounded_quantifier_does_not_overflow_stack_on_long_input() {
+    let mut facets = Facets::default();
+    facets.pattern = Some(vec!["a*".to_string()]);
+    let long = "a".repeat(100_000);
+    let _: Token = parse(&long, &facets);
+}
+
+#[test]
+fn pattern_facet_malformed_bound_is_a_hard_error() {
+    // `{5,2}` can never be satisfied (max < min); treated as a compile
+    // failure rather than a quantifier that silently matches nothing.
+    let mut facets = Facets::default();
+    facets.pattern = Some(vec!["a{5,2}".to_string()]);
+    let err = parse_err::<Token>("aaaaa", &facets);
+    assert_eq!(err.facet, "pattern");
+}
+
+#[test]
+fn integer_parses_arbitrary_precision_values() {
+    let n: Integer = parse("123456789012345678901234567890", &Facets::default());
+    assert_eq!(format!("{}", n.0), "123456789012345678901234567890");
+}
+
+#[test]
+fn non_negative_integer_floors_a_negative_min_inclusive_at_zero() {
+    let mut facets = Facets::default();
+    facets.min_inclusive = Some(BigFloatNotNaN::from(BigDecimal::from(-5)));
+    let n: NonNegativeInteger = parse("0", &facets);
+    assert_eq!(format!("{}", n.0), "0");
+    let err = parse_err::<NonNegativeInteger>("-1", &facets);
+    assert_eq!(err.facet, "minInclusive");
+}
+
+#[test]
+fn positive_integer_rejects_zero() {
+    let err = parse_err::<PositiveInteger>("0", &Facets::default());
+    assert_eq!(err.facet, "minInclusive");
+}
+
+#[test]
+fn xml_string_collapse_facet_squashes_whitespace_runs() {
+    let mut facets = Facets::default();
+    facets.white_space = Some(WhiteSpace::Collapse);
+    let s: XmlString = parse("hello   \t\n  world", &facets);
+    assert_eq!(&*s.0, "hello world");
+}
+
+#[test]
+fn xml_string_defaults_to_preserving_whitespace() {
+    let s: XmlString = parse("hello   world", &Facets::default());
+    assert_eq!(&*s.0, "hello   world");
+}
+
+#[test]
+fn decimal_total_digits_facet_is_enforced() {
+    let mut facets = Facets::default();
+    facets.total_digits = Some(3);
+    let _: Decimal = parse("1.20", &facets);
+    let err = parse_err::<Decimal>("12.345", &facets);
+    assert_eq!(err.facet, "totalDigits");
+}
+
+#[test]
+fn decimal_supports_ordering_and_arithmetic() {
+    let a: Decimal = parse("1.5", &Facets::default());
+    let b: Decimal = parse("2.5", &Facets::default());
+    assert!(a < b);
+    assert_eq!(a.clone() + b.clone(), parse("4", &Facets::default()));
+    assert_eq!(b - a, parse("1", &Facets::default()));
+}
+
+#[test]
+fn decimal_trailing_zero_does_not_count_as_a_significant_digit() {
+    // "1.20" has 2 significant digits (1, 2), not 3: a trailing fractional
+    // zero is not significant in XSD's canonical digit-counting rules.
+    let mut facets = Facets::default();
+    facets.total_digits = Some(2);
+    let _: Decimal = parse("1.20", &facets);
+}
+
+#[test]
+fn xml_string_enumeration_facet_rejects_values_outside_the_set() {
+    let mut facets = Facets::default();
+    facets.enumeration = Some(vec!["red", "green", "blue"]);
+    let _: XmlString = parse("green", &facets);
+    let err = parse_err::<XmlString>("purple", &facets);
+    assert_eq!(err.facet, "enumeration");
+    assert_eq!(err.offset, 6);
+    assert_eq!(err.expected, format!("one of {:?}", facets.enumeration.as_ref().unwrap()));
+    assert_eq!(err.actual, format!("{:?}", "purple"));
+}
+
+#[test]
+fn xml_string_length_facet_requires_an_exact_length() {
+    let mut facets = Facets::default();
+    facets.length = Some(3);
+    let _: XmlString = parse("abc", &facets);
+    let err = parse_err::<XmlString>("abcd", &facets);
+    assert_eq!(err.facet, "length");
+    assert_eq!(err.offset, 4);
+    assert_eq!(err.expected, "length 3");
+}
+
+#[test]
+fn xml_string_min_length_facet_rejects_short_values() {
+    let mut facets = Facets::default();
+    facets.min_length = Some(3);
+    let _: XmlString = parse("abc", &facets);
+    let err = parse_err::<XmlString>("ab", &facets);
+    assert_eq!(err.facet, "minLength");
+    assert_eq!(err.offset, 2);
+    assert_eq!(err.expected, "length >= 3");
+}
+
+#[test]
+fn xml_string_max_length_facet_rejects_long_values() {
+    let mut facets = Facets::default();
+    facets.max_length = Some(3);
+    let _: XmlString = parse("abc", &facets);
+    let err = parse_err::<XmlString>("abcd", &facets);
+    assert_eq!(err.facet, "maxLength");
+    assert_eq!(err.offset, 4);
+    assert_eq!(err.expected, "length <= 3");
+    assert_eq!(err.actual, format!("{:?}", "abcd"));
+}
+
+#[test]
+fn timezone_with_multi_byte_utf8_suffix_is_rejected_not_panicking() {
+    // A malformed timezone suffix containing a multi-byte UTF-8 character
+    // must return None, not panic on a mid-codepoint slice boundary.
+    assert!(DateTime::parse_self_xml_str("2024-01-01T00:00:00+1\u{e9}:00", &mut TestContext::default(), &ParentContext::default(), &Facets::default()).unwrap().is_none());
+}